@@ -18,7 +18,7 @@ use cipher::{
         typenum::{bit::B1, Double, Sum, Unsigned},
         ArrayLength,
     },
-    BlockCipherKey, CipherKey, NewCipher, StreamCipher,
+    BlockCipherKey, CipherKey, NewCipher, Nonce, StreamCipher,
 };
 use crypto_mac::{Key as MacKey, Mac, NewMac};
 use std::ops::{Add, Shl};
@@ -30,6 +30,23 @@ use generic_array::typenum::U1;
 #[cfg(feature = "block-cipher")]
 use std::marker::PhantomData;
 
+#[cfg(feature = "aead")]
+pub use aead::{self, AeadCore, AeadInPlace, Error as AeadError, NewAead};
+#[cfg(feature = "aead")]
+use aead::{Key as AeadKey, Nonce as AeadNonce, Tag as AeadTag};
+#[cfg(feature = "aead")]
+use generic_array::typenum::U0;
+#[cfg(feature = "aead")]
+use subtle::{Choice, ConstantTimeEq};
+
+#[cfg(feature = "block-padding")]
+pub use block_padding::{self, Padding};
+#[cfg(feature = "block-padding")]
+use block_padding::{PadError, UnpadError};
+
+#[cfg(feature = "zeroize")]
+pub use zeroize::{self, Zeroize};
+
 fn xor_in_place(a: &mut [u8], b: &[u8]) {
     for (ai, bi) in a.iter_mut().zip(b.iter()) {
         *ai ^= *bi;
@@ -50,23 +67,60 @@ where
     xor_in_place(left, &*tmp);
 }
 
-fn right_xor_assign_stream<S>(left: &[u8], right: &mut [u8], stream_key_half: &CipherKey<S>)
-where
+fn right_xor_assign_stream<S>(
+    left: &[u8],
+    right: &mut [u8],
+    stream_key_half: &CipherKey<S>,
+    nonce: &Nonce<S>,
+) where
     S: StreamCipher + NewCipher,
 {
     let stream_key = xor(left, stream_key_half);
 
-    // TODO: a potential change in the future: allow for optionally passing non-zero IVs
-    let mut s = S::new(
-        &GenericArray::from_exact_iter(stream_key).unwrap(),
-        &Default::default(),
-    );
+    let mut s = S::new(&GenericArray::from_exact_iter(stream_key).unwrap(), nonce);
     s.apply_keystream(right); // XORs keystream with data
 }
 
+#[cfg(feature = "block-padding")]
+fn counter_tweak<S>(counter: u64) -> Nonce<S>
+where
+    S: NewCipher,
+{
+    let mut tweak = Nonce::<S>::default();
+    let len = tweak.len();
+    let counter_bytes = counter.to_be_bytes();
+    let copy_len = counter_bytes.len().min(len);
+    tweak[len - copy_len..].copy_from_slice(&counter_bytes[counter_bytes.len() - copy_len..]);
+    tweak
+}
+
 #[derive(Debug)]
 pub struct InvalidBlockLength;
 
+/// Error returned by the fallible constructors of [`Lioness`] and
+/// [`BlockLioness`](struct.BlockLioness.html) (when the `block-cipher` feature is enabled).
+#[derive(Debug)]
+pub enum LionessError {
+    /// The provided key slice did not match the cipher's expected key size.
+    InvalidKeyLength,
+    /// `H::OutputSize` must be at least `S::KeySize` for the key-splitting layout to be valid.
+    IncompatibleKeyAndDigestSize,
+    /// `BlockLioness`'s fixed block size `N` must be larger than `H::OutputSize`.
+    BlockSizeTooSmall,
+}
+
+/// Error returned by [`LionessBuffered::encrypt`] and [`LionessBuffered::decrypt`].
+#[cfg(feature = "block-padding")]
+#[derive(Debug)]
+pub enum LionessBufferedError {
+    /// The ciphertext length wasn't a non-zero multiple of the fixed block size `N`.
+    InvalidBlockLength,
+    /// The plaintext's final chunk could not be padded out to the fixed block size `N`.
+    Pad(PadError),
+    /// The deciphered padding was malformed.
+    Unpad(UnpadError),
+}
+
 pub struct Lioness<S, H>
 where
     S: StreamCipher + NewCipher,
@@ -83,7 +137,24 @@ where
     S: StreamCipher + NewCipher,
     H: Mac + NewMac,
 {
+    /// Enciphers `block` in place using a zero stream-cipher nonce.
+    ///
+    /// This is a thin wrapper around [`Lioness::encrypt_block_with_tweak`] for callers that
+    /// don't need to process more than a single block under a given key.
     pub fn encrypt_block(&self, block: &mut [u8]) -> Result<(), InvalidBlockLength> {
+        self.encrypt_block_with_tweak(block, &Default::default())
+    }
+
+    /// Enciphers `block` in place, feeding `tweak` into the two stream-cipher passes as the
+    /// underlying `S::NonceSize` nonce.
+    ///
+    /// Passing a distinct `tweak` (e.g. a per-block counter) allows many wide blocks to be
+    /// enciphered under the same key without reusing the internal keystreams.
+    pub fn encrypt_block_with_tweak(
+        &self,
+        block: &mut [u8],
+        tweak: &Nonce<S>,
+    ) -> Result<(), InvalidBlockLength> {
         if block.len() <= H::OutputSize::to_usize() {
             return Err(InvalidBlockLength);
         }
@@ -91,13 +162,13 @@ where
         let (left, right) = block.split_at_mut(H::OutputSize::to_usize());
 
         //// R = R ^ S(L ^ K1)
-        right_xor_assign_stream::<S>(left, right, &self.k1);
+        right_xor_assign_stream::<S>(left, right, &self.k1, tweak);
 
         //// L = L ^ H(K2, R)
         left_xor_assign_digest::<H>(left, right, &self.k2);
 
         //// R = R ^ S(L ^ K3)
-        right_xor_assign_stream::<S>(left, right, &self.k3);
+        right_xor_assign_stream::<S>(left, right, &self.k3, tweak);
 
         //// L = L ^ H(K4, R)
         left_xor_assign_digest::<H>(left, right, &self.k4);
@@ -105,8 +176,22 @@ where
         Ok(())
     }
 
-    // TODO: return error if block is too small
+    /// Deciphers `block` in place using a zero stream-cipher nonce.
+    ///
+    /// This is a thin wrapper around [`Lioness::decrypt_block_with_tweak`] for callers that
+    /// don't need to process more than a single block under a given key.
     pub fn decrypt_block(&self, block: &mut [u8]) -> Result<(), InvalidBlockLength> {
+        self.decrypt_block_with_tweak(block, &Default::default())
+    }
+
+    /// Deciphers `block` in place, feeding `tweak` into the two stream-cipher passes as the
+    /// underlying `S::NonceSize` nonce. Must match the `tweak` passed to
+    /// [`Lioness::encrypt_block_with_tweak`].
+    pub fn decrypt_block_with_tweak(
+        &self,
+        block: &mut [u8],
+        tweak: &Nonce<S>,
+    ) -> Result<(), InvalidBlockLength> {
         if block.len() <= H::OutputSize::to_usize() {
             return Err(InvalidBlockLength);
         }
@@ -117,19 +202,19 @@ where
         left_xor_assign_digest::<H>(left, right, &self.k4);
 
         //// R = R ^ S(L ^ K3)
-        right_xor_assign_stream::<S>(left, right, &self.k3);
+        right_xor_assign_stream::<S>(left, right, &self.k3, tweak);
 
         //// L = L ^ H(K2, R)
         left_xor_assign_digest::<H>(left, right, &self.k2);
 
         //// R = R ^ S(L ^ K1)
-        right_xor_assign_stream::<S>(left, right, &self.k1);
+        right_xor_assign_stream::<S>(left, right, &self.k1, tweak);
 
         Ok(())
     }
 }
 
-impl<S, H> NewBlockCipher for Lioness<S, H>
+impl<S, H> Lioness<S, H>
 where
     S: StreamCipher + NewCipher,
     H: Mac + NewMac,
@@ -142,19 +227,78 @@ where
     Sum<S::KeySize, H::KeySize>: Shl<B1>,
     Double<Sum<S::KeySize, H::KeySize>>: ArrayLength<u8>,
 {
-    type KeySize = Double<Sum<S::KeySize, H::KeySize>>;
-
-    fn new(key: &BlockCipherKey<Self>) -> Self {
-        assert!(H::OutputSize::to_usize() >= S::KeySize::to_usize());
+    /// Fallibly constructs a new [`Lioness`] instance, returning a [`LionessError`] rather than
+    /// panicking if `H::OutputSize` is too small for `S::KeySize` to fit the key-splitting layout.
+    pub fn try_new(key: &BlockCipherKey<Self>) -> Result<Self, LionessError> {
+        if H::OutputSize::to_usize() < S::KeySize::to_usize() {
+            return Err(LionessError::IncompatibleKeyAndDigestSize);
+        }
 
         let sck = S::KeySize::to_usize();
         let hk = H::KeySize::to_usize();
-        Lioness {
+        Ok(Lioness {
             k1: GenericArray::clone_from_slice(&key[..sck]),
             k2: GenericArray::clone_from_slice(&key[sck..sck + hk]),
             k3: GenericArray::clone_from_slice(&key[sck + hk..2 * sck + hk]),
             k4: GenericArray::clone_from_slice(&key[2 * sck + hk..]),
+        })
+    }
+
+    /// Fallibly constructs a new [`Lioness`] instance from a variable-length key slice,
+    /// returning a [`LionessError`] if its length doesn't match [`NewBlockCipher::KeySize`] or if
+    /// `H::OutputSize` is too small for `S::KeySize`.
+    pub fn new_from_slice(key: &[u8]) -> Result<Self, LionessError> {
+        if key.len() != <Self as NewBlockCipher>::KeySize::to_usize() {
+            return Err(LionessError::InvalidKeyLength);
         }
+
+        Self::try_new(GenericArray::from_slice(key))
+    }
+}
+
+impl<S, H> NewBlockCipher for Lioness<S, H>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+
+    // requirements for being able to sum key lengths
+    S::KeySize: Add<H::KeySize>,
+    Sum<S::KeySize, H::KeySize>: ArrayLength<u8>,
+
+    // requirements for being able to double the sum
+    Sum<S::KeySize, H::KeySize>: Shl<B1>,
+    Double<Sum<S::KeySize, H::KeySize>>: ArrayLength<u8>,
+{
+    type KeySize = Double<Sum<S::KeySize, H::KeySize>>;
+
+    fn new(key: &BlockCipherKey<Self>) -> Self {
+        Self::try_new(key).expect("invalid Lioness key parameters")
+    }
+}
+
+/// Wipes the round keys `k1..k4` from memory.
+#[cfg(feature = "zeroize")]
+impl<S, H> Zeroize for Lioness<S, H>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+{
+    fn zeroize(&mut self) {
+        self.k1.zeroize();
+        self.k2.zeroize();
+        self.k3.zeroize();
+        self.k4.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<S, H> Drop for Lioness<S, H>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+{
+    fn drop(&mut self) {
+        self.zeroize();
     }
 }
 
@@ -168,6 +312,47 @@ where
     block_size: PhantomData<*const N>,
 }
 
+#[cfg(feature = "block-cipher")]
+impl<S, H, N> BlockLioness<S, H, N>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+    N: ArrayLength<u8>,
+
+    // requirements for being able to sum key lengths
+    S::KeySize: Add<H::KeySize>,
+    Sum<S::KeySize, H::KeySize>: ArrayLength<u8>,
+
+    // requirements for being able to double the sum
+    Sum<S::KeySize, H::KeySize>: Shl<B1>,
+    Double<Sum<S::KeySize, H::KeySize>>: ArrayLength<u8>,
+{
+    /// Fallibly constructs a new [`BlockLioness`] instance, returning a [`LionessError`] rather
+    /// than panicking if `N` is too small for `H::OutputSize`, or if the inner [`Lioness`]'s own
+    /// key/digest size invariant doesn't hold.
+    pub fn try_new(key: &BlockCipherKey<Self>) -> Result<Self, LionessError> {
+        if N::to_usize() <= H::OutputSize::to_usize() {
+            return Err(LionessError::BlockSizeTooSmall);
+        }
+
+        Ok(BlockLioness {
+            inner: Lioness::try_new(key)?,
+            block_size: Default::default(),
+        })
+    }
+
+    /// Fallibly constructs a new [`BlockLioness`] instance from a variable-length key slice,
+    /// returning a [`LionessError`] if its length doesn't match [`NewBlockCipher::KeySize`] or if
+    /// any of the usual invariants checked by [`Self::try_new`] don't hold.
+    pub fn new_from_slice(key: &[u8]) -> Result<Self, LionessError> {
+        if key.len() != <Self as NewBlockCipher>::KeySize::to_usize() {
+            return Err(LionessError::InvalidKeyLength);
+        }
+
+        Self::try_new(GenericArray::from_slice(key))
+    }
+}
+
 #[cfg(feature = "block-cipher")]
 impl<S, H, N> NewBlockCipher for BlockLioness<S, H, N>
 where
@@ -186,12 +371,32 @@ where
     type KeySize = Double<Sum<S::KeySize, H::KeySize>>;
 
     fn new(key: &BlockCipherKey<Self>) -> Self {
-        assert!(N::to_usize() > H::OutputSize::to_usize());
+        Self::try_new(key).expect("invalid BlockLioness key parameters")
+    }
+}
 
-        BlockLioness {
-            inner: Lioness::new(key),
-            block_size: Default::default(),
-        }
+/// Wipes the inner [`Lioness`]'s round keys from memory.
+#[cfg(feature = "zeroize")]
+#[cfg(feature = "block-cipher")]
+impl<S, H, N> Zeroize for BlockLioness<S, H, N>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+{
+    fn zeroize(&mut self) {
+        self.inner.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg(feature = "block-cipher")]
+impl<S, H, N> Drop for BlockLioness<S, H, N>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+{
+    fn drop(&mut self) {
+        self.zeroize();
     }
 }
 
@@ -230,6 +435,272 @@ where
     }
 }
 
+/// An "encode-then-encipher" authenticated cipher built on top of [`Lioness`].
+///
+/// A message is authenticated by appending `H::OutputSize` zero bytes to it before enciphering
+/// it as a single wide block with [`Lioness`] (a strong wide-block PRP): on success, decryption
+/// can only recover the original zero padding if the ciphertext, nonce and associated data are
+/// all unmodified, so the trailing zero bytes double up as an integrity tag. Unlike [`Lioness`]
+/// itself, every message is enciphered under a fresh `k1..k4` derived from the master key, the
+/// nonce and the associated data, so distinct `(nonce, associated_data)` pairs never share a
+/// keystream.
+///
+/// The plaintext must be non-empty: an empty plaintext would produce a wide block no longer
+/// than `H::OutputSize`, which [`Lioness::encrypt_block`] always rejects, so empty-plaintext
+/// (AD-only) messages aren't supported.
+#[cfg(feature = "aead")]
+pub struct LionessAead<S, H>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+{
+    key: MacKey<H>,
+    _stream: std::marker::PhantomData<S>,
+}
+
+#[cfg(feature = "aead")]
+impl<S, H> NewAead for LionessAead<S, H>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+{
+    type KeySize = H::KeySize;
+
+    fn new(key: &AeadKey<Self>) -> Self {
+        LionessAead {
+            key: key.clone(),
+            _stream: std::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "aead")]
+impl<S, H> AeadCore for LionessAead<S, H>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+{
+    type NonceSize = S::NonceSize;
+    type TagSize = H::OutputSize;
+    type CiphertextOverhead = U0;
+}
+
+#[cfg(feature = "aead")]
+impl<S, H> LionessAead<S, H>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+
+    // requirements for being able to sum key lengths
+    S::KeySize: Add<H::KeySize>,
+    Sum<S::KeySize, H::KeySize>: ArrayLength<u8>,
+
+    // requirements for being able to double the sum
+    Sum<S::KeySize, H::KeySize>: Shl<B1>,
+    Double<Sum<S::KeySize, H::KeySize>>: ArrayLength<u8>,
+{
+    /// Derives a one-off `Lioness` key for this `(nonce, associated_data)` pair by running `H`
+    /// keyed on the master key over `nonce || associated_data`, expanding the digest output
+    /// (via a counter) until there's enough material to fill `k1..k4`.
+    fn derive_message_key(
+        &self,
+        nonce: &AeadNonce<Self>,
+        associated_data: &[u8],
+    ) -> BlockCipherKey<Lioness<S, H>> {
+        let required_len = Double::<Sum<S::KeySize, H::KeySize>>::to_usize();
+
+        let mut expanded = Vec::with_capacity(required_len);
+        let mut counter: u8 = 0;
+        while expanded.len() < required_len {
+            let mut h = H::new(&self.key);
+            h.update(&[counter]);
+            h.update(nonce);
+            h.update(associated_data);
+            expanded.extend_from_slice(&h.finalize().into_bytes());
+            counter += 1;
+        }
+        expanded.truncate(required_len);
+
+        GenericArray::from_exact_iter(expanded).unwrap()
+    }
+}
+
+#[cfg(feature = "aead")]
+impl<S, H> AeadInPlace for LionessAead<S, H>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+
+    // requirements for being able to sum key lengths
+    S::KeySize: Add<H::KeySize>,
+    Sum<S::KeySize, H::KeySize>: ArrayLength<u8>,
+
+    // requirements for being able to double the sum
+    Sum<S::KeySize, H::KeySize>: Shl<B1>,
+    Double<Sum<S::KeySize, H::KeySize>>: ArrayLength<u8>,
+{
+    /// `buffer` must be non-empty: the underlying [`Lioness::encrypt_block`] only accepts wide
+    /// blocks longer than `H::OutputSize`, so an empty plaintext (which would produce a wide
+    /// block of exactly `H::OutputSize` bytes) is rejected with [`AeadError`] rather than
+    /// enciphered.
+    fn encrypt_in_place_detached(
+        &self,
+        nonce: &AeadNonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<AeadTag<Self>, AeadError> {
+        let tau = H::OutputSize::to_usize();
+        let cipher = Lioness::<S, H>::new(&self.derive_message_key(nonce, associated_data));
+
+        let mut wide_block = Vec::with_capacity(buffer.len() + tau);
+        wide_block.extend_from_slice(buffer);
+        wide_block.extend(std::iter::repeat(0u8).take(tau));
+
+        cipher
+            .encrypt_block(&mut wide_block)
+            .map_err(|_| AeadError)?;
+
+        let ciphertext_len = buffer.len();
+        buffer.copy_from_slice(&wide_block[..ciphertext_len]);
+        Ok(AeadTag::<Self>::clone_from_slice(
+            &wide_block[ciphertext_len..],
+        ))
+    }
+
+    /// `buffer` must correspond to a non-empty plaintext that was produced by
+    /// [`Self::encrypt_in_place_detached`]; see its docs for why empty plaintexts aren't
+    /// supported.
+    fn decrypt_in_place_detached(
+        &self,
+        nonce: &AeadNonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &AeadTag<Self>,
+    ) -> Result<(), AeadError> {
+        let cipher = Lioness::<S, H>::new(&self.derive_message_key(nonce, associated_data));
+
+        let mut wide_block = Vec::with_capacity(buffer.len() + tag.len());
+        wide_block.extend_from_slice(buffer);
+        wide_block.extend_from_slice(tag);
+
+        cipher
+            .decrypt_block(&mut wide_block)
+            .map_err(|_| AeadError)?;
+
+        let (plaintext, redundancy) = wide_block.split_at(buffer.len());
+        let redundancy_is_zero: Choice = redundancy
+            .iter()
+            .fold(Choice::from(1u8), |acc, &byte| acc & byte.ct_eq(&0u8));
+        if !bool::from(redundancy_is_zero) {
+            return Err(AeadError);
+        }
+
+        buffer.copy_from_slice(plaintext);
+        Ok(())
+    }
+}
+
+/// A [`Lioness`]-based wide-block cipher for messages of arbitrary length.
+///
+/// `N` is the fixed chunk size the message is split into (after padding with `P`), and each
+/// chunk is enciphered under a distinct per-chunk counter tweak via
+/// [`Lioness::encrypt_block_with_tweak`], so a message of any length can be processed under a
+/// single key without keystream reuse between chunks.
+#[cfg(feature = "block-padding")]
+pub struct LionessBuffered<S, H, N, P>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+{
+    inner: Lioness<S, H>,
+    block_size: std::marker::PhantomData<*const N>,
+    padding: std::marker::PhantomData<*const P>,
+}
+
+#[cfg(feature = "block-padding")]
+impl<S, H, N, P> NewBlockCipher for LionessBuffered<S, H, N, P>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+    N: ArrayLength<u8>,
+
+    // requirements for being able to sum key lengths
+    S::KeySize: Add<H::KeySize>,
+    Sum<S::KeySize, H::KeySize>: ArrayLength<u8>,
+
+    // requirements for being able to double the sum
+    Sum<S::KeySize, H::KeySize>: Shl<B1>,
+    Double<Sum<S::KeySize, H::KeySize>>: ArrayLength<u8>,
+{
+    type KeySize = Double<Sum<S::KeySize, H::KeySize>>;
+
+    fn new(key: &BlockCipherKey<Self>) -> Self {
+        assert!(N::to_usize() > H::OutputSize::to_usize());
+
+        LionessBuffered {
+            inner: Lioness::new(key),
+            block_size: Default::default(),
+            padding: Default::default(),
+        }
+    }
+}
+
+#[cfg(feature = "block-padding")]
+impl<S, H, N, P> LionessBuffered<S, H, N, P>
+where
+    S: StreamCipher + NewCipher,
+    H: Mac + NewMac,
+    N: ArrayLength<u8>,
+    P: Padding,
+{
+    /// Pads `data` up to a multiple of `N` bytes and enciphers it chunk by chunk, each chunk
+    /// under a distinct counter tweak.
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, LionessBufferedError> {
+        let n = N::to_usize();
+
+        let full_chunks_len = (data.len() / n) * n;
+        let mut buffer = data[..full_chunks_len].to_vec();
+
+        let mut last_chunk = vec![0u8; n];
+        let remainder = &data[full_chunks_len..];
+        last_chunk[..remainder.len()].copy_from_slice(remainder);
+        P::pad(&mut last_chunk, remainder.len(), n).map_err(LionessBufferedError::Pad)?;
+        buffer.extend_from_slice(&last_chunk);
+
+        for (i, chunk) in buffer.chunks_mut(n).enumerate() {
+            let tweak = counter_tweak::<S>(i as u64);
+            self.inner
+                .encrypt_block_with_tweak(chunk, &tweak)
+                .map_err(|_| LionessBufferedError::InvalidBlockLength)?;
+        }
+
+        Ok(buffer)
+    }
+
+    /// Deciphers `data` chunk by chunk and strips the padding added by [`Self::encrypt`].
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, LionessBufferedError> {
+        let n = N::to_usize();
+        if data.is_empty() || data.len() % n != 0 {
+            return Err(LionessBufferedError::InvalidBlockLength);
+        }
+
+        let mut buffer = data.to_vec();
+        for (i, chunk) in buffer.chunks_mut(n).enumerate() {
+            let tweak = counter_tweak::<S>(i as u64);
+            self.inner
+                .decrypt_block_with_tweak(chunk, &tweak)
+                .map_err(|_| LionessBufferedError::InvalidBlockLength)?;
+        }
+
+        let (full_chunks, last_chunk) = buffer.split_at(buffer.len() - n);
+        let unpadded_last = P::unpad(last_chunk).map_err(LionessBufferedError::Unpad)?;
+
+        let mut plaintext = full_chunks.to_vec();
+        plaintext.extend_from_slice(unpadded_last);
+        Ok(plaintext)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -243,6 +714,25 @@ mod tests {
         Cipher::new(&zero_key);
     }
 
+    #[test]
+    fn new_from_slice_rejects_a_key_of_the_wrong_length() {
+        type Cipher = Lioness<ChaCha20, Blake3>;
+
+        let key = vec![0u8; <Cipher as NewBlockCipher>::KeySize::to_usize() - 1];
+        assert!(matches!(
+            Cipher::new_from_slice(&key),
+            Err(LionessError::InvalidKeyLength)
+        ));
+    }
+
+    #[test]
+    fn new_from_slice_accepts_a_key_of_the_right_length() {
+        type Cipher = Lioness<ChaCha20, Blake3>;
+
+        let key = vec![0u8; <Cipher as NewBlockCipher>::KeySize::to_usize()];
+        assert!(Cipher::new_from_slice(&key).is_ok());
+    }
+
     #[test]
     fn encryption_is_reciprocal_to_decryption_for_chacha20_blake3_variant() {
         type Cipher = Lioness<ChaCha20, Blake3>;
@@ -262,6 +752,26 @@ mod tests {
         assert_eq!(data.to_vec(), block.to_vec());
     }
 
+    #[test]
+    fn encryption_is_reciprocal_to_decryption_with_non_zero_tweak() {
+        type Cipher = Lioness<ChaCha20, Blake3>;
+
+        let key = GenericArray::from(b"my-awesome-key-that-is-perfect-length-to-work-with-chacha20-and-blake3-lioness-cipher-after-adding-a-little-bit-of-extra-padding".to_owned());
+
+        let data = b"Hello there! This is some test data that has length at least as long as the digest size of Blake3.";
+        let mut block = *data;
+        let tweak = GenericArray::from([1u8; 12]);
+
+        let cipher = Cipher::new(&key);
+        cipher.encrypt_block_with_tweak(&mut block, &tweak).unwrap();
+
+        // make sure encryption actually did something
+        assert_ne!(data.to_vec(), block.to_vec());
+
+        cipher.decrypt_block_with_tweak(&mut block, &tweak).unwrap();
+        assert_eq!(data.to_vec(), block.to_vec());
+    }
+
     #[cfg(feature = "block-cipher")]
     #[test]
     fn cipher_creation_works_for_block_chacha20_blake3_variant() {
@@ -294,5 +804,106 @@ mod tests {
         assert_eq!(data.to_vec(), block.to_vec());
     }
 
+    #[cfg(feature = "aead")]
+    #[test]
+    fn aead_encryption_is_reciprocal_to_decryption_for_chacha20_blake3_variant() {
+        type Cipher = LionessAead<ChaCha20, Blake3>;
+
+        let key = GenericArray::default();
+        let nonce = GenericArray::from([7u8; 12]);
+        let associated_data = b"some associated data";
+
+        let data = b"Hello there! This is some test data that has length at least as long as the digest size of Blake3.";
+        let mut buffer = *data;
+
+        let cipher = Cipher::new(&key);
+        let tag = cipher
+            .encrypt_in_place_detached(&nonce, associated_data, &mut buffer)
+            .unwrap();
+
+        // make sure encryption actually did something
+        assert_ne!(data.to_vec(), buffer.to_vec());
+
+        cipher
+            .decrypt_in_place_detached(&nonce, associated_data, &mut buffer, &tag)
+            .unwrap();
+        assert_eq!(data.to_vec(), buffer.to_vec());
+    }
+
+    #[cfg(feature = "aead")]
+    #[test]
+    fn aead_decryption_fails_on_tampered_associated_data() {
+        type Cipher = LionessAead<ChaCha20, Blake3>;
+
+        let key = GenericArray::default();
+        let nonce = GenericArray::from([7u8; 12]);
+
+        let data = b"Hello there! This is some test data that has length at least as long as the digest size of Blake3.";
+        let mut buffer = *data;
+
+        let cipher = Cipher::new(&key);
+        let tag = cipher
+            .encrypt_in_place_detached(&nonce, b"correct aad", &mut buffer)
+            .unwrap();
+
+        assert!(cipher
+            .decrypt_in_place_detached(&nonce, b"wrong aad", &mut buffer, &tag)
+            .is_err());
+    }
+
+    #[cfg(feature = "block-padding")]
+    #[test]
+    fn buffered_encryption_is_reciprocal_to_decryption_for_unaligned_message() {
+        use block_padding::Pkcs7;
+        use generic_array::typenum::U64;
+
+        type Cipher = LionessBuffered<ChaCha20, Blake3, U64, Pkcs7>;
+
+        let key = GenericArray::from(b"my-awesome-key-that-is-perfect-length-to-work-with-chacha20-and-blake3-lioness-cipher-after-adding-a-little-bit-of-extra-padding".to_owned());
+        let data = b"This message is deliberately not a multiple of the 64-byte chunk size used by the cipher.";
+
+        let cipher = Cipher::new(&key);
+        let ciphertext = cipher.encrypt(data).unwrap();
+
+        // make sure encryption actually did something and padded up to a whole number of chunks
+        assert_ne!(data.to_vec(), ciphertext);
+        assert_eq!(ciphertext.len() % 64, 0);
+
+        let plaintext = cipher.decrypt(&ciphertext).unwrap();
+        assert_eq!(data.to_vec(), plaintext);
+    }
+
+    #[cfg(feature = "block-padding")]
+    #[test]
+    fn buffered_encryption_is_reciprocal_to_decryption_for_multi_chunk_message() {
+        use block_padding::ZeroPadding;
+        use generic_array::typenum::U64;
+
+        type Cipher = LionessBuffered<ChaCha20, Blake3, U64, ZeroPadding>;
+
+        let key = GenericArray::from(b"my-awesome-key-that-is-perfect-length-to-work-with-chacha20-and-blake3-lioness-cipher-after-adding-a-little-bit-of-extra-padding".to_owned());
+        let data = [42u8; 200];
+
+        let cipher = Cipher::new(&key);
+        let ciphertext = cipher.encrypt(&data).unwrap();
+        let plaintext = cipher.decrypt(&ciphertext).unwrap();
+        assert_eq!(data.to_vec(), plaintext);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_clears_all_round_keys() {
+        type Cipher = Lioness<ChaCha20, Blake3>;
+
+        let key = GenericArray::from(b"my-awesome-key-that-is-perfect-length-to-work-with-chacha20-and-blake3-lioness-cipher-after-adding-a-little-bit-of-extra-padding".to_owned());
+        let mut cipher = Cipher::new(&key);
+        cipher.zeroize();
+
+        assert!(cipher.k1.iter().all(|&b| b == 0));
+        assert!(cipher.k2.iter().all(|&b| b == 0));
+        assert!(cipher.k3.iter().all(|&b| b == 0));
+        assert!(cipher.k4.iter().all(|&b| b == 0));
+    }
+
     // TODO: further testing with proper vectors, edge cases, etc.
 }